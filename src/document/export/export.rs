@@ -0,0 +1,143 @@
+use std::collections::HashSet;
+
+use crate::document::section::section::Section;
+
+/// An edge between two headers, directed for `Ancestors`/`Children` pairs
+/// (`(ancestor, descendant)`) or canonically ordered for `Related` pairs.
+type Edge = (String, String);
+
+/// Renders a batch of resolved sections as a Graphviz DOT directed graph:
+/// one node per header, with each `Ancestors`/`Children` link drawn once as
+/// an edge from the ancestor down to the descendant (labelled `Children`,
+/// whichever side listed it), and `Related` edges drawn undirected and dashed.
+///
+/// Assumes `update_related`/`update_related_batch` has already run, so
+/// every related entry is a valid header.
+pub fn to_dot(sections: &[Section]) -> String {
+    let mut out = String::from("digraph notes {\n");
+    for section in sections {
+        out.push_str(&format!("    {:?};\n", section.get_header()));
+    }
+
+    let (parent_child, related) = collect_edges(sections);
+    for (parent, child) in &parent_child {
+        out.push_str(&format!("    {:?} -> {:?} [label=\"Children\"];\n", parent, child));
+    }
+    for (a, b) in &related {
+        out.push_str(&format!("    {:?} -> {:?} [label=\"Related\", dir=none, style=dashed];\n", a, b));
+    }
+
+    out.push_str("}\n");
+    out
+}
+
+/// Renders a batch of resolved sections as a Mermaid `graph TD` diagram,
+/// using the same edge conventions as `to_dot`.
+pub fn to_mermaid(sections: &[Section]) -> String {
+    let mut out = String::from("graph TD\n");
+    for section in sections {
+        out.push_str(&format!("    {};\n", escape_mermaid(&section.get_header())));
+    }
+
+    let (parent_child, related) = collect_edges(sections);
+    for (parent, child) in &parent_child {
+        out.push_str(&format!("    {} --> {}\n", escape_mermaid(parent), escape_mermaid(child)));
+    }
+    for (a, b) in &related {
+        out.push_str(&format!("    {} -.- {}\n", escape_mermaid(a), escape_mermaid(b)));
+    }
+
+    out
+}
+
+/// Walks every section's relational entries and resolves them into
+/// deduplicated edges: `(ancestor, descendant)` pairs for the combined
+/// `Ancestors`/`Children` relation (an `Ancestors` entry on one side and its
+/// reciprocal `Children` entry on the other describe the same directed
+/// edge), and unordered `(a, b)` pairs (`a <= b`) for `Related`, so a
+/// symmetric link isn't rendered twice.
+fn collect_edges(sections: &[Section]) -> (Vec<Edge>, Vec<Edge>) {
+    let mut parent_child: HashSet<Edge> = HashSet::new();
+    let mut related: HashSet<Edge> = HashSet::new();
+
+    for section in sections {
+        let header = section.get_header();
+        for (category, targets) in section.get_related() {
+            for target in targets {
+                match category.as_str() {
+                    "Ancestors" => { parent_child.insert((target.clone(), header.clone())); },
+                    "Children" => { parent_child.insert((header.clone(), target.clone())); },
+                    "Related" => {
+                        let pair = if header <= *target {
+                            (header.clone(), target.clone())
+                        } else {
+                            (target.clone(), header.clone())
+                        };
+                        related.insert(pair);
+                    },
+                    _ => {},
+                }
+            }
+        }
+    }
+
+    let mut parent_child: Vec<Edge> = parent_child.into_iter().collect();
+    parent_child.sort();
+    let mut related: Vec<Edge> = related.into_iter().collect();
+    related.sort();
+    (parent_child, related)
+}
+
+/// Escapes a header for use as Mermaid node text. Mermaid's quoted-text
+/// grammar has no backslash escape (unlike DOT), so a literal `"` would
+/// terminate the quoted string early; replace it with the `#quot;` HTML
+/// entity Mermaid recognizes instead, then wrap the result in quotes.
+fn escape_mermaid(header: &str) -> String {
+    format!("\"{}\"", header.replace('"', "#quot;"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn section(header: &str, category: &str, targets: &[&str]) -> Section {
+        let mut section = Section::new(header.to_string());
+        for target in targets {
+            section.push_related(category.to_string(), target.to_string());
+        }
+        section
+    }
+
+    #[test]
+    fn to_dot_dedupes_reciprocal_ancestors_children_and_related_edges() {
+        let sections = vec![
+            section("A", "Children", &["B"]),
+            {
+                let mut b = section("B", "Ancestors", &["A"]);
+                b.push_related("Related".to_string(), "C".to_string());
+                b
+            },
+            section("C", "Related", &["B"]),
+        ];
+
+        let dot = to_dot(&sections);
+
+        assert_eq!(dot.matches("->").count(), 2);
+        assert_eq!(dot.matches("\"A\" -> \"B\"").count(), 1);
+        assert_eq!(dot.matches("dir=none").count(), 1);
+    }
+
+    #[test]
+    fn to_mermaid_declares_isolated_nodes_and_escapes_quotes() {
+        let sections = vec![
+            section("Lone Section", "Children", &[]),
+            section("He said \"hi\"", "Children", &[]),
+        ];
+
+        let mermaid = to_mermaid(&sections);
+
+        assert!(mermaid.contains("\"Lone Section\";"));
+        assert!(mermaid.contains("\"He said #quot;hi#quot;\";"));
+        assert!(!mermaid.contains("\\\""));
+    }
+}
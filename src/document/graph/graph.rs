@@ -0,0 +1,245 @@
+use std::collections::HashMap;
+
+use crate::document::section::section::Section;
+
+/// A stable integer key identifying a `Section` within a `SectionGraph`.
+pub type Key = usize;
+
+/// Related references that couldn't be resolved cleanly, reported instead
+/// of silently guessing or panicking so the caller can see every broken
+/// link at once.
+#[derive(Debug, Default)]
+pub struct DanglingReport {
+    /// `(header of the section holding the reference, unresolved reference)`
+    pub dangling: Vec<(String, String)>,
+    /// `(header of the section holding the reference, shorthand, every header it prefix-matches)`
+    pub ambiguous: Vec<(String, String, Vec<String>)>,
+}
+
+/// The outcome of resolving a raw related-reference string to a header.
+enum Resolution {
+    /// Resolved to exactly one section, either a perfect match or a single
+    /// prefix match.
+    Exact(Key),
+    /// Matched no known header.
+    Unresolved,
+    /// Prefix-matched more than one header; every candidate is listed.
+    Ambiguous(Vec<String>),
+}
+
+/// A cross-section graph built on top of a parsed batch of `Section`s.
+///
+/// Unlike `Section::related`, which stores raw header strings, every
+/// reference here is resolved to the stable `Key` of its target, so walking
+/// from one section to another is O(1) per hop instead of a string lookup.
+pub struct SectionGraph {
+    sections: Vec<Section>,
+    headers: HashMap<String, Key>,
+    ancestors: HashMap<Key, Vec<Key>>,
+    children: HashMap<Key, Vec<Key>>,
+    related: HashMap<Key, Vec<Key>>,
+}
+
+impl SectionGraph {
+    /// Builds a graph from a batch of parsed sections, assigning each a
+    /// stable key and resolving every `Ancestors`/`Children`/`Related` string
+    /// into the key of its target.
+    ///
+    /// # Returns
+    ///
+    /// The graph, plus a report of any references that matched no header or
+    /// prefix-matched more than one.
+    pub fn build(sections: Vec<Section>) -> (Self, DanglingReport) {
+        let headers_vec: Vec<String> = sections.iter().map(|s| s.get_header()).collect();
+        let headers: HashMap<String, Key> = headers_vec
+            .iter()
+            .enumerate()
+            .map(|(key, header)| (header.clone(), key))
+            .collect();
+
+        let mut ancestors: HashMap<Key, Vec<Key>> = HashMap::new();
+        let mut children: HashMap<Key, Vec<Key>> = HashMap::new();
+        let mut related: HashMap<Key, Vec<Key>> = HashMap::new();
+        let mut dangling: Vec<(String, String)> = Vec::new();
+        let mut ambiguous: Vec<(String, String, Vec<String>)> = Vec::new();
+
+        for (key, section) in sections.iter().enumerate() {
+            for (category, entries) in section.get_related() {
+                let bucket = match category.as_str() {
+                    "Ancestors" => &mut ancestors,
+                    "Children" => &mut children,
+                    "Related" => &mut related,
+                    _ => continue,
+                };
+                for entry in entries {
+                    match Self::resolve_header(entry, &headers, &headers_vec) {
+                        Resolution::Exact(target) => bucket.entry(key).or_default().push(target),
+                        Resolution::Unresolved => dangling.push((section.get_header(), entry.clone())),
+                        Resolution::Ambiguous(candidates) => {
+                            ambiguous.push((section.get_header(), entry.clone(), candidates));
+                        },
+                    }
+                }
+            }
+        }
+
+        (
+            Self { sections, headers, ancestors, children, related },
+            DanglingReport { dangling, ambiguous },
+        )
+    }
+
+    /// Resolves a raw header string to its key, reusing the same
+    /// perfect-match-then-prefix-match strategy as `Section::process_related_string`
+    /// and `Section::find_matching_header`:
+    /// an exact match via `headers` first, then every prefix match in
+    /// `headers_vec`'s document order (not `HashMap` iteration order, which
+    /// is randomized per process and would make an ambiguous shorthand
+    /// resolve non-deterministically across runs). More than one prefix
+    /// match is reported as `Ambiguous` instead of silently picking one.
+    fn resolve_header(s: &str, headers: &HashMap<String, Key>, headers_vec: &[String]) -> Resolution {
+        if let Some(&key) = headers.get(s) {
+            return Resolution::Exact(key);
+        }
+        let candidates: Vec<String> = headers_vec
+            .iter()
+            .filter(|header| header.starts_with(s))
+            .cloned()
+            .collect();
+        match candidates.len() {
+            0 => Resolution::Unresolved,
+            1 => Resolution::Exact(headers[&candidates[0]]),
+            _ => Resolution::Ambiguous(candidates),
+        }
+    }
+
+    /// Returns the key of the section whose header is exactly `header`, if
+    /// one exists. Shorthands are not prefix-matched here.
+    pub fn resolve(&self, header: &str) -> Option<Key> {
+        self.headers.get(header).copied()
+    }
+
+    /// Returns the section stored under `key`, if any.
+    pub fn get(&self, key: Key) -> Option<&Section> {
+        self.sections.get(key)
+    }
+
+    /// Keys of the ancestors of `key`.
+    pub fn ancestors(&self, key: Key) -> &[Key] {
+        self.ancestors.get(&key).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    /// Keys of the children of `key`.
+    pub fn children(&self, key: Key) -> &[Key] {
+        self.children.get(&key).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    /// Keys of the sections related to `key`.
+    pub fn related(&self, key: Key) -> &[Key] {
+        self.related.get(&key).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    /// Iterates over every section directly reachable from `key` via any
+    /// relational category.
+    pub fn neighbors(&self, key: Key) -> impl Iterator<Item = &Section> {
+        self.ancestors(key)
+            .iter()
+            .chain(self.children(key).iter())
+            .chain(self.related(key).iter())
+            .filter_map(move |k| self.sections.get(*k))
+    }
+
+    /// Walks `Ancestors` links from `key` up to a root, returning the
+    /// breadcrumb chain from root to `key` (inclusive).
+    pub fn breadcrumbs(&self, key: Key) -> Vec<Key> {
+        let mut chain = vec![key];
+        let mut current = key;
+        while let Some(&parent) = self.ancestors(current).first() {
+            if chain.contains(&parent) {
+                break;
+            }
+            chain.push(parent);
+            current = parent;
+        }
+        chain.reverse();
+        chain
+    }
+
+    /// Computes the transitive closure of every key reachable from `key` via
+    /// any relational category.
+    pub fn transitive_closure(&self, key: Key) -> Vec<Key> {
+        let mut seen = vec![key];
+        let mut frontier = vec![key];
+        while let Some(current) = frontier.pop() {
+            for next in self
+                .ancestors(current)
+                .iter()
+                .chain(self.children(current).iter())
+                .chain(self.related(current).iter())
+            {
+                if !seen.contains(next) {
+                    seen.push(*next);
+                    frontier.push(*next);
+                }
+            }
+        }
+        seen
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn section(header: &str, category: &str, targets: &[&str]) -> Section {
+        let mut section = Section::new(header.to_string());
+        for target in targets {
+            section.push_related(category.to_string(), target.to_string());
+        }
+        section
+    }
+
+    #[test]
+    fn build_resolves_related_headers_and_supports_traversal() {
+        let sections = vec![
+            section("Root", "Children", &["Child"]),
+            section("Child", "Ancestors", &["Root"]),
+        ];
+        let (graph, report) = SectionGraph::build(sections);
+
+        assert!(report.dangling.is_empty());
+        assert!(report.ambiguous.is_empty());
+
+        let root = graph.resolve("Root").unwrap();
+        let child = graph.resolve("Child").unwrap();
+
+        assert_eq!(graph.children(root), &[child]);
+        assert_eq!(graph.ancestors(child), &[root]);
+        assert_eq!(
+            graph.neighbors(root).map(Section::get_header).collect::<Vec<_>>(),
+            vec!["Child".to_string()]
+        );
+        assert_eq!(graph.breadcrumbs(child), vec![root, child]);
+        assert_eq!(graph.transitive_closure(root), vec![root, child]);
+    }
+
+    #[test]
+    fn build_reports_dangling_and_ambiguous_references_instead_of_guessing() {
+        let sections = vec![
+            section("Origin", "Related", &["Vec", "Nonexistent"]),
+            section("Vector Space", "Related", &[]),
+            section("Vector Algebra", "Related", &[]),
+        ];
+        let (graph, report) = SectionGraph::build(sections);
+
+        let origin = graph.resolve("Origin").unwrap();
+        assert!(graph.related(origin).is_empty());
+
+        assert_eq!(report.dangling, vec![("Origin".to_string(), "Nonexistent".to_string())]);
+        assert_eq!(report.ambiguous.len(), 1);
+        let (header, shorthand, candidates) = &report.ambiguous[0];
+        assert_eq!(header, "Origin");
+        assert_eq!(shorthand, "Vec");
+        assert_eq!(candidates, &vec!["Vector Space".to_string(), "Vector Algebra".to_string()]);
+    }
+}
@@ -0,0 +1,138 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::document::graph::graph::Key;
+use crate::document::section::section::Section;
+
+/// An in-memory inverted index over a batch of parsed `Section`s, supporting
+/// simple TF-IDF ranked search without any external search service.
+pub struct SearchIndex {
+    sections: Vec<Section>,
+    postings: HashMap<String, HashSet<Key>>,
+    term_counts: Vec<HashMap<String, usize>>,
+}
+
+impl SearchIndex {
+    /// Tokenizes the header and every information entry of each section,
+    /// building an inverted index from term to the keys of sections
+    /// containing it.
+    pub fn build(sections: Vec<Section>) -> Self {
+        let mut postings: HashMap<String, HashSet<Key>> = HashMap::new();
+        let mut term_counts: Vec<HashMap<String, usize>> = Vec::with_capacity(sections.len());
+
+        for (key, section) in sections.iter().enumerate() {
+            let mut counts: HashMap<String, usize> = HashMap::new();
+            for token in Self::tokenize(&section.get_header()) {
+                *counts.entry(token).or_insert(0) += 1;
+            }
+            for entries in section.get_information().values() {
+                for text in entries {
+                    for token in Self::tokenize(text) {
+                        *counts.entry(token).or_insert(0) += 1;
+                    }
+                }
+            }
+            for term in counts.keys() {
+                postings.entry(term.clone()).or_default().insert(key);
+            }
+            term_counts.push(counts);
+        }
+
+        Self { sections, postings, term_counts }
+    }
+
+    /// Returns the section stored under `key`, if any.
+    pub fn get(&self, key: Key) -> Option<&Section> {
+        self.sections.get(key)
+    }
+
+    /// Lowercases and splits on non-alphanumeric characters.
+    fn tokenize(s: &str) -> Vec<String> {
+        s.to_lowercase()
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|t| !t.is_empty())
+            .map(|t| t.to_string())
+            .collect()
+    }
+
+    /// Ranks sections by a TF-IDF score against `terms`: for each query
+    /// term, a section scores (term frequency in that section) × ln(total
+    /// sections / sections containing the term), summed across terms.
+    pub fn query(&self, terms: &str) -> Vec<(Key, f32)> {
+        let total = self.sections.len() as f32;
+        let mut scores: HashMap<Key, f32> = HashMap::new();
+
+        for term in Self::tokenize(terms) {
+            let containing = match self.postings.get(&term) {
+                Some(containing) if !containing.is_empty() => containing,
+                _ => continue,
+            };
+            let idf = (total / containing.len() as f32).ln();
+            for &key in containing {
+                let tf = *self.term_counts[key].get(&term).unwrap_or(&0) as f32;
+                *scores.entry(key).or_insert(0.0) += tf * idf;
+            }
+        }
+
+        let mut ranked: Vec<(Key, f32)> = scores.into_iter().collect();
+        ranked.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap());
+        ranked
+    }
+
+    /// Searches only the information entries under `category` (e.g.
+    /// `"Definition"`), returning each section with at least one entry that
+    /// contains a query term, alongside the specific entries that hit.
+    /// Results are in corpus order and are not scored or ranked.
+    pub fn query_category<'a>(&'a self, terms: &str, category: &str) -> Vec<(&'a Section, Vec<&'a str>)> {
+        let query_terms: HashSet<String> = Self::tokenize(terms).into_iter().collect();
+        let mut matches: Vec<(&Section, Vec<&str>)> = Vec::new();
+
+        for section in &self.sections {
+            let entries = match section.get_information().get(category) {
+                Some(entries) => entries,
+                None => continue,
+            };
+            let hits: Vec<&str> = entries
+                .iter()
+                .filter(|text| Self::tokenize(text).iter().any(|token| query_terms.contains(token)))
+                .map(|text| text.as_str())
+                .collect();
+            if !hits.is_empty() {
+                matches.push((section, hits));
+            }
+        }
+
+        matches
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn section_with_definition(header: &str, text: &str) -> Section {
+        let mut section = Section::new(header.to_string());
+        section.push_information("Definition".to_string(), text.to_string());
+        section
+    }
+
+    #[test]
+    fn query_ranks_sections_by_tf_idf() {
+        let sections = vec![
+            section_with_definition("Alpha", "cat dog"),
+            section_with_definition("Beta", "cat"),
+            section_with_definition("Gamma", "bird"),
+        ];
+        let index = SearchIndex::build(sections);
+
+        let ranked = index.query("cat dog");
+
+        // cat: idf = ln(3/2) ≈ 0.405465; dog: idf = ln(3/1) ≈ 1.098612.
+        // Alpha has both (tf=1 each): 0.405465 + 1.098612 ≈ 1.504077.
+        // Beta has only cat (tf=1): 0.405465. Gamma has neither, so it's absent.
+        assert_eq!(ranked.len(), 2);
+        assert_eq!(index.get(ranked[0].0).unwrap().get_header(), "Alpha");
+        assert!((ranked[0].1 - 1.504077).abs() < 1e-4);
+        assert_eq!(index.get(ranked[1].0).unwrap().get_header(), "Beta");
+        assert!((ranked[1].1 - 0.405465).abs() < 1e-4);
+    }
+}
@@ -1,11 +1,43 @@
 use std::collections::{HashMap, HashSet};
-use std::fmt::{Display, Formatter};
+use std::fmt::{self, Display, Formatter};
 use serde::{Serialize};
 
 use crate::document::information::information::Information;
 
+/// Errors that can occur while parsing a `Section` or resolving its related entries.
+#[derive(Debug, PartialEq)]
+pub enum SectionError {
+    /// The paragraph passed to `Section::parse` had no lines at all.
+    EmptyParagraph,
+    /// A line of the paragraph failed to parse as an `Information` entry.
+    BadInformationLine { line: String, cause: String },
+    /// A related shorthand matched no known header.
+    UnresolvedReference(String),
+    /// A related shorthand matched more than one header by prefix.
+    AmbiguousReference { shorthand: String, candidates: Vec<String> },
+}
+
+impl Display for SectionError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            SectionError::EmptyParagraph => write!(f, "paragraph is empty, expected a header line"),
+            SectionError::BadInformationLine { line, cause } => {
+                write!(f, "failed to parse information line {:?}: {}", line, cause)
+            },
+            SectionError::UnresolvedReference(shorthand) => {
+                write!(f, "{:?} does not match any known header", shorthand)
+            },
+            SectionError::AmbiguousReference { shorthand, candidates } => {
+                write!(f, "{:?} matches multiple headers: {:?}", shorthand, candidates)
+            },
+        }
+    }
+}
+
+impl std::error::Error for SectionError {}
+
 /// A section is a topic in the notes (e.g. Identity Matrix).
-#[derive(Serialize)]
+#[derive(Debug, Serialize)]
 pub struct Section {
     header: String,
     information: HashMap<String, Vec<String>>,
@@ -18,25 +50,27 @@ impl Section {
     /// # Arguments
     ///
     /// * `s` - A paragraph string to be parsed.
-    pub fn parse(s: &str) -> Result<Self, ()> {
+    pub fn parse(s: &str) -> Result<Self, SectionError> {
         let raw_lines: Vec<&str> = s.split("\n").collect();
         let mut lines_iter = raw_lines.into_iter();
         /// Header is the first line of the paragraph.
         let header: String = lines_iter
             .next()
-            .unwrap()
+            .filter(|_| !s.is_empty())
+            .ok_or(SectionError::EmptyParagraph)?
             .to_string();
 
         let mut information: HashMap<String, Vec<String>> = HashMap::new();
         let mut related: HashMap<String, Vec<String>> = HashMap::new();
-        let information_vec = lines_iter
-            .skip(0)
-            .map(Information::parse)
-            .map(|i| i.unwrap());
-
-        for i in information_vec {
-            let category: String = i.get_category();
-            let text: String = i.get_text();
+
+        for line in lines_iter {
+            let parsed = Information::parse(line)
+                .map_err(|cause| SectionError::BadInformationLine {
+                    line: line.to_string(),
+                    cause: cause.to_string(),
+                })?;
+            let category: String = parsed.get_category();
+            let text: String = parsed.get_text();
             match category.as_str() {
                 /// These three are considered "Related" sections, as they point to other sections.
                 "Ancestors" | "Children" | "Related" => {
@@ -68,6 +102,16 @@ impl Section {
         self.header.to_string()
     }
 
+    /// Returns the raw `Ancestors`/`Children`/`Related` entries for this section.
+    pub fn get_related(&self) -> &HashMap<String, Vec<String>> {
+        &self.related
+    }
+
+    /// Returns the non-relational information entries for this section.
+    pub fn get_information(&self) -> &HashMap<String, Vec<String>> {
+        &self.information
+    }
+
     /// Updates strings in related so that they match the header of another section.
     /// This is the case because sometimes I want to type shorthands of headers to save time.
     ///
@@ -81,37 +125,184 @@ impl Section {
     ///
     /// # Returns
     ///
-    /// A new section with updated related field.
-    pub fn update_related(section: Section, headers_set: &HashSet<String>, headers_vec: &Vec<String>) -> Self {
+    /// A new section with updated related field, or every error encountered
+    /// while resolving its shorthands (not just the first).
+    pub fn update_related(section: Section, headers_set: &HashSet<String>, headers_vec: &Vec<String>) -> Result<Self, Vec<SectionError>> {
         let header: String = section.header;
         let information: HashMap<String, Vec<String>> = section.information;
-        let related: HashMap<String, Vec<String>> = section.related
-            .into_iter()
-            .map(|e| Section::process_related_entry(e, headers_set, headers_vec))
-            .collect();
-        Self { header, information, related }
+        let mut related: HashMap<String, Vec<String>> = HashMap::new();
+        let mut errors: Vec<SectionError> = Vec::new();
+        for entry in section.related {
+            match Section::process_related_entry(entry, headers_set, headers_vec) {
+                Ok((k, v)) => {
+                    related.insert(k, v);
+                },
+                Err(mut e) => errors.append(&mut e),
+            }
+        }
+        if errors.is_empty() {
+            Ok(Self { header, information, related })
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Resolves every section's related references against the full corpus,
+    /// aggregating all unresolved/ambiguous references, within a section and
+    /// across the whole document, into a single `Vec` of errors instead of
+    /// stopping at the first one, so an author can fix every broken link in
+    /// one pass.
+    pub fn update_related_batch(sections: Vec<Section>, headers_set: &HashSet<String>, headers_vec: &Vec<String>) -> Result<Vec<Self>, Vec<SectionError>> {
+        let mut resolved = Vec::with_capacity(sections.len());
+        let mut errors = Vec::new();
+        for section in sections {
+            match Section::update_related(section, headers_set, headers_vec) {
+                Ok(section) => resolved.push(section),
+                Err(mut e) => errors.append(&mut e),
+            }
+        }
+        if errors.is_empty() {
+            Ok(resolved)
+        } else {
+            Err(errors)
+        }
     }
 
-    fn process_related_entry((k, v): (String, Vec<String>), headers_set: &HashSet<String>, headers_vec: &Vec<String>) -> (String, Vec<String>) {
-        (k, v.into_iter()
-            .map(|s| Section::process_related_string(s, headers_set, headers_vec))
-            .collect())
+    fn process_related_entry((k, v): (String, Vec<String>), headers_set: &HashSet<String>, headers_vec: &Vec<String>) -> Result<(String, Vec<String>), Vec<SectionError>> {
+        let mut resolved = Vec::with_capacity(v.len());
+        let mut errors = Vec::new();
+        for s in v {
+            match Section::process_related_string(s, headers_set, headers_vec) {
+                Ok(header) => resolved.push(header),
+                Err(e) => errors.push(e),
+            }
+        }
+        if errors.is_empty() {
+            Ok((k, resolved))
+        } else {
+            Err(errors)
+        }
     }
 
-    fn process_related_string(s: String, headers_set: &HashSet<String>, headers_vec: &Vec<String>) -> String {
+    fn process_related_string(s: String, headers_set: &HashSet<String>, headers_vec: &Vec<String>) -> Result<String, SectionError> {
         match headers_set.contains(&s) {
-            true => s,
-            false => Section::find_matching_header(s.clone(), headers_vec)
+            true => Ok(s),
+            false => Section::find_matching_header(s, headers_vec)
         }
     }
 
-    fn find_matching_header(s: String, headers_vec: &Vec<String>) -> String {
-        headers_vec
+    fn find_matching_header(s: String, headers_vec: &Vec<String>) -> Result<String, SectionError> {
+        let candidates: Vec<String> = headers_vec
             .into_iter()
             .filter(|h| h.starts_with(&s))
-            .next()
-            .unwrap()
-            .clone()
+            .cloned()
+            .collect();
+        match candidates.len() {
+            0 => Err(SectionError::UnresolvedReference(s)),
+            1 => Ok(candidates.into_iter().next().unwrap()),
+            _ => Err(SectionError::AmbiguousReference { shorthand: s, candidates }),
+        }
+    }
+
+    /// Constructs an empty section with no information or related entries.
+    pub fn new(header: String) -> Self {
+        Self { header, information: HashMap::new(), related: HashMap::new() }
+    }
+
+    /// Appends a piece of information under `category`.
+    pub fn push_information(&mut self, category: String, text: String) {
+        self.information.entry(category).or_default().push(text);
+    }
+
+    /// Appends a related header under `category` (`Ancestors`/`Children`/`Related`).
+    pub fn push_related(&mut self, category: String, header: String) {
+        self.related.entry(category).or_default().push(header);
+    }
+
+    /// Removes every entry under `category` from `information`, returning it if present.
+    pub fn remove_information(&mut self, category: &str) -> Option<Vec<String>> {
+        self.information.remove(category)
+    }
+
+    /// Returns a mutable handle to the entries under `category`, checking
+    /// `information` first and falling back to `related`.
+    pub fn entries_mut(&mut self, category: &str) -> Option<&mut Vec<String>> {
+        if self.information.contains_key(category) {
+            self.information.get_mut(category)
+        } else {
+            self.related.get_mut(category)
+        }
+    }
+
+    /// Renames this section's header in place.
+    ///
+    /// This only updates `self`; use [`Section::rename_across_corpus`] to
+    /// also rewrite every other section's `related` entries that pointed at
+    /// the old header.
+    pub fn rename_header(&mut self, new: String) {
+        self.header = new;
+    }
+
+    /// Renames the header of `sections[index]` and rewrites every other
+    /// section's `related` entries that pointed at the old header, the
+    /// inverse of the shorthand resolution `update_related` performs.
+    ///
+    /// # Returns
+    ///
+    /// `None` if `index` is out of bounds, leaving `sections` untouched.
+    pub fn rename_across_corpus(sections: &mut [Section], index: usize, new: String) -> Option<()> {
+        let old = sections.get(index)?.header.clone();
+        sections[index].rename_header(new.clone());
+        for (i, section) in sections.iter_mut().enumerate() {
+            if i == index {
+                continue;
+            }
+            for entries in section.related.values_mut() {
+                for entry in entries.iter_mut() {
+                    if *entry == old {
+                        *entry = new.clone();
+                    }
+                }
+            }
+        }
+        Some(())
+    }
+
+    /// Infers the reciprocal side of every relational link across the
+    /// corpus: a `Children` entry pointing at header H inserts this
+    /// section's header into H's `Ancestors`, and vice versa, and `Related`
+    /// is made symmetric the same way. Deduplicates so repeated runs are
+    /// idempotent.
+    ///
+    /// Must run after `update_related`/`update_related_batch` have resolved
+    /// shorthands to full headers.
+    pub fn infer_reciprocal_links(sections: &mut [Section]) {
+        let headers: HashMap<String, usize> = sections
+            .iter()
+            .enumerate()
+            .map(|(i, s)| (s.header.clone(), i))
+            .collect();
+
+        let mut additions: Vec<(usize, String, String)> = Vec::new();
+        for section in sections.iter() {
+            let header = section.header.clone();
+            for (category, reciprocal) in [("Children", "Ancestors"), ("Ancestors", "Children"), ("Related", "Related")] {
+                if let Some(targets) = section.related.get(category) {
+                    for target in targets {
+                        if let Some(&index) = headers.get(target) {
+                            additions.push((index, reciprocal.to_string(), header.clone()));
+                        }
+                    }
+                }
+            }
+        }
+
+        for (index, category, header) in additions {
+            let entries = sections[index].related.entry(category).or_default();
+            if !entries.contains(&header) {
+                entries.push(header);
+            }
+        }
     }
 }
 
@@ -124,4 +315,67 @@ impl Display for Section {
             .join("\n")
         )
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn update_related_batch_reports_every_broken_reference_in_one_section() {
+        let mut origin = Section::new("Origin".to_string());
+        origin.push_related("Related".to_string(), "Vec".to_string());
+        origin.push_related("Related".to_string(), "Nonexistent".to_string());
+
+        let headers_vec = vec![
+            "Origin".to_string(),
+            "Vector Space".to_string(),
+            "Vector Algebra".to_string(),
+        ];
+        let headers_set: HashSet<String> = headers_vec.iter().cloned().collect();
+
+        let sections = vec![
+            origin,
+            Section::new("Vector Space".to_string()),
+            Section::new("Vector Algebra".to_string()),
+        ];
+
+        let errors = Section::update_related_batch(sections, &headers_set, &headers_vec)
+            .expect_err("expected both broken references to be reported");
+
+        assert_eq!(errors.len(), 2);
+        assert!(errors.iter().any(|e| matches!(
+            e,
+            SectionError::AmbiguousReference { shorthand, candidates }
+                if shorthand == "Vec" && candidates.len() == 2
+        )));
+        assert!(errors.iter().any(|e| matches!(
+            e,
+            SectionError::UnresolvedReference(shorthand) if shorthand == "Nonexistent"
+        )));
+    }
+
+    #[test]
+    fn infer_reciprocal_links_is_idempotent() {
+        let mut parent = Section::new("Parent".to_string());
+        parent.push_related("Children".to_string(), "Child".to_string());
+        let child = Section::new("Child".to_string());
+
+        let mut sections = vec![parent, child];
+        Section::infer_reciprocal_links(&mut sections);
+
+        assert_eq!(
+            sections[1].get_related().get("Ancestors"),
+            Some(&vec!["Parent".to_string()])
+        );
+
+        let after_first_pass: Vec<HashMap<String, Vec<String>>> =
+            sections.iter().map(|s| s.get_related().clone()).collect();
+
+        Section::infer_reciprocal_links(&mut sections);
+        let after_second_pass: Vec<HashMap<String, Vec<String>>> =
+            sections.iter().map(|s| s.get_related().clone()).collect();
+
+        assert_eq!(after_first_pass, after_second_pass);
+    }
 }
\ No newline at end of file